@@ -0,0 +1,562 @@
+//! Proc-macro companion to `define_language!`.
+//!
+//! `define_language!` is convenient for simple languages, but it can't express
+//! variants that mix data fields and child `Id`s, and it struggles with
+//! lifetimes, const generics, and `where`-clauses on the enum itself. This
+//! crate adds a `#[derive(Language)]` that works on a normal `enum` by
+//! walking each variant's fields (in the spirit of `synstructure`), rather
+//! than parsing a macro-specific grammar. The generated impls are built from
+//! the same `LanguageChildren`, `FromOp`, and `FromOpError` machinery that
+//! `define_language!` uses, so the two are interchangeable.
+//!
+//! # Example
+//!
+//! ```
+//! use egg::{Id, Symbol};
+//! use egg_derive::Language;
+//!
+//! #[derive(Language, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+//! enum Lang {
+//!     #[op = "+"]
+//!     Add([Id; 2]),
+//!     #[op = "let"]
+//!     Let {
+//!         name: Symbol,
+//!         bindings: [Id; 2],
+//!     },
+//!     Num(i32),
+//! }
+//! ```
+//!
+//! For each variant, the derive splits its fields into the children (the
+//! `Id`-typed fields) and the rest (the data). A variant's children must live
+//! in a single field: either a bare `Id`, or a type implementing
+//! `LanguageChildren` (`[Id; N]`, `Vec<Id>`, or `Box<[Id]>`) for a fixed- or
+//! variable-size child list. Scattering children across several separate
+//! fields isn't supported, since there'd be no way to hand back a contiguous
+//! `&[Id]`/`&mut [Id]` over them; `#[derive(Language)]` rejects that shape at
+//! compile time instead of panicking at run time — group the children into
+//! one array or `Vec<Id>` field. In particular, a variant shaped like
+//! `Let { name: Symbol, binding: Id, body: Id }`, with `binding` and `body`
+//! as two separate `Id` fields, is *not* expressible under this derive; the
+//! `Let` example above uses `bindings: [Id; 2]` instead for exactly that
+//! reason, not because it's a more idiomatic spelling of the same thing.
+
+extern crate proc_macro;
+
+mod rewrite_checked;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, FieldsNamed, FieldsUnnamed, Variant};
+
+/// Derives [`Language`], [`Display`], and [`FromOp`] for an `enum` by
+/// field-walking its variants, the way `define_language!` does for its own
+/// grammar. See the crate-level docs for the attributes this understands.
+///
+/// [`Language`]: https://docs.rs/egg/latest/egg/trait.Language.html
+/// [`Display`]: std::fmt::Display
+/// [`FromOp`]: https://docs.rs/egg/latest/egg/trait.FromOp.html
+#[proc_macro_derive(Language, attributes(op))]
+pub fn derive_language(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return syn::Error::new_spanned(&input, "#[derive(Language)] only supports enums")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let variants: Vec<VariantInfo> = match data.variants.iter().map(VariantInfo::new).collect() {
+        Ok(variants) => variants,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let matches_arms = variants.iter().map(VariantInfo::matches_arm);
+    let children_arms = variants.iter().map(|v| v.children_arm(false));
+    let children_mut_arms = variants.iter().map(|v| v.children_arm(true));
+    let display_arms = variants.iter().map(VariantInfo::display_arm);
+    let from_op_arms = variants.iter().map(VariantInfo::from_op_arm);
+
+    let expanded = quote! {
+        impl #impl_generics egg::Language for #name #ty_generics #where_clause {
+            type Discriminant = ::std::mem::Discriminant<Self>;
+
+            #[inline(always)]
+            fn discriminant(&self) -> Self::Discriminant {
+                ::std::mem::discriminant(self)
+            }
+
+            #[inline(always)]
+            fn matches(&self, other: &Self) -> bool {
+                ::std::mem::discriminant(self) == ::std::mem::discriminant(other)
+                    && match (self, other) {
+                        #(#matches_arms)*
+                        _ => false,
+                    }
+            }
+
+            fn children(&self) -> &[egg::Id] {
+                match self {
+                    #(#children_arms)*
+                }
+            }
+
+            fn children_mut(&mut self) -> &mut [egg::Id] {
+                match self {
+                    #(#children_mut_arms)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    #(#display_arms)*
+                }
+            }
+        }
+
+        impl #impl_generics egg::FromOp for #name #ty_generics #where_clause {
+            type Error = egg::FromOpError;
+
+            fn from_op(op: &str, children: ::std::vec::Vec<egg::Id>) -> ::std::result::Result<Self, Self::Error> {
+                #(#from_op_arms)*
+                Err(egg::FromOpError::new(op, children))
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// What kind of field a variant field was classified as while walking it.
+enum FieldKind {
+    /// An `Id`-typed field; contributes to the variant's children.
+    Child,
+    /// Anything else; contributes to the variant's data/equality check.
+    Data,
+}
+
+struct Field {
+    kind: FieldKind,
+    /// How to refer to this field's binding inside a generated match arm.
+    binding: syn::Ident,
+    ty: syn::Type,
+}
+
+struct VariantInfo {
+    ident: syn::Ident,
+    op: Option<syn::LitStr>,
+    fields: Vec<Field>,
+    style: Fields,
+}
+
+impl VariantInfo {
+    fn new(variant: &Variant) -> syn::Result<Self> {
+        let op = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("op"))
+            .map(parse_op_attr)
+            .transpose()?;
+
+        let fields: Vec<Field> = match &variant.fields {
+            Fields::Named(FieldsNamed { named, .. }) => named
+                .iter()
+                .map(|f| Field {
+                    kind: classify(&f.ty),
+                    binding: f.ident.clone().unwrap(),
+                    ty: f.ty.clone(),
+                })
+                .collect(),
+            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => unnamed
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Field {
+                    kind: classify(&f.ty),
+                    binding: quote::format_ident!("__egg_field_{}", i),
+                    ty: f.ty.clone(),
+                })
+                .collect(),
+            Fields::Unit => Vec::new(),
+        };
+
+        if fields.iter().filter(|f| matches!(f.kind, FieldKind::Child)).count() > 1 {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "a variant may have at most one child field, since `children`/`children_mut` \
+                 must return a contiguous slice; group the children into a single `[Id; N]`, \
+                 `Vec<Id>`, or `Box<[Id]>` field instead",
+            ));
+        }
+
+        Ok(VariantInfo {
+            ident: variant.ident.clone(),
+            op,
+            fields,
+            style: variant.fields.clone(),
+        })
+    }
+
+    fn pattern(&self, name: &syn::Ident) -> TokenStream2 {
+        let ident = &self.ident;
+        match &self.style {
+            Fields::Unit => quote! { #name::#ident },
+            Fields::Unnamed(_) => {
+                let bindings = self.fields.iter().map(|f| &f.binding);
+                quote! { #name::#ident(#(#bindings),*) }
+            }
+            Fields::Named(_) => {
+                let bindings = self.fields.iter().map(|f| &f.binding);
+                quote! { #name::#ident { #(#bindings),* } }
+            }
+        }
+    }
+
+    fn matches_arm(&self) -> TokenStream2 {
+        let lhs_pat = rebind(self, "__egg_self_");
+        let rhs_pat = rebind(self, "__egg_other_");
+        let data_checks = self
+            .fields
+            .iter()
+            .filter(|f| matches!(f.kind, FieldKind::Data))
+            .map(|f| {
+                let lhs_bind = quote::format_ident!("__egg_self_{}", f.binding);
+                let rhs_bind = quote::format_ident!("__egg_other_{}", f.binding);
+                quote! { #lhs_bind == #rhs_bind }
+            });
+        // A variant's child field may be a variable-length `LanguageChildren`
+        // (`Vec<Id>`/`Box<[Id]>`), so two e-nodes of the same variant can
+        // still differ in arity; check lengths the same way `define_language!`
+        // does, or `matches()` would merge e-nodes with different arities.
+        let child_checks = self
+            .fields
+            .iter()
+            .filter(|f| matches!(f.kind, FieldKind::Child))
+            .map(|f| {
+                let lhs_bind = quote::format_ident!("__egg_self_{}", f.binding);
+                let rhs_bind = quote::format_ident!("__egg_other_{}", f.binding);
+                quote! { egg::LanguageChildren::len(#lhs_bind) == egg::LanguageChildren::len(#rhs_bind) }
+            });
+        let checks: Vec<_> = data_checks.chain(child_checks).collect();
+        let cond = if checks.is_empty() {
+            quote! { true }
+        } else {
+            quote! { #(#checks)&&* }
+        };
+        quote! { (#lhs_pat, #rhs_pat) => #cond, }
+    }
+
+    fn children_arm(&self, mutable: bool) -> TokenStream2 {
+        let pattern = self.pattern(&quote::format_ident!("Self"));
+        let child_fields: Vec<_> = self
+            .fields
+            .iter()
+            .filter(|f| matches!(f.kind, FieldKind::Child))
+            .collect();
+
+        // `VariantInfo::new` already rejected variants with more than one
+        // child field, so only the empty/single-field shapes are reachable.
+        let body = match child_fields.as_slice() {
+            [] => {
+                if mutable {
+                    quote! { &mut [] }
+                } else {
+                    quote! { &[] }
+                }
+            }
+            [field] if is_array_or_slice(&field.ty) => {
+                let binding = &field.binding;
+                if mutable {
+                    quote! { egg::LanguageChildren::as_mut_slice(#binding) }
+                } else {
+                    quote! { egg::LanguageChildren::as_slice(#binding) }
+                }
+            }
+            [field] => {
+                let binding = &field.binding;
+                if mutable {
+                    quote! { ::std::slice::from_mut(#binding) }
+                } else {
+                    quote! { ::std::slice::from_ref(#binding) }
+                }
+            }
+            _ => unreachable!("VariantInfo::new rejects variants with more than one child field"),
+        };
+
+        quote! { #pattern => #body, }
+    }
+
+    fn display_arm(&self) -> TokenStream2 {
+        let pattern = self.pattern(&quote::format_ident!("Self"));
+        if let Some(op) = &self.op {
+            quote! { #pattern => f.write_str(#op), }
+        } else {
+            let data_field = self.fields.iter().find(|f| matches!(f.kind, FieldKind::Data));
+            match data_field {
+                Some(field) => {
+                    let binding = &field.binding;
+                    quote! { #pattern => ::std::fmt::Display::fmt(#binding, f), }
+                }
+                None => {
+                    let ident = &self.ident;
+                    let name_str = ident.to_string();
+                    quote! { #pattern => f.write_str(#name_str), }
+                }
+            }
+        }
+    }
+
+    fn from_op_arm(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let op_lit = self.op.clone();
+        let child_fields: Vec<_> = self
+            .fields
+            .iter()
+            .filter(|f| matches!(f.kind, FieldKind::Child))
+            .collect();
+        let data_fields: Vec<_> = self
+            .fields
+            .iter()
+            .filter(|f| matches!(f.kind, FieldKind::Data))
+            .collect();
+
+        let child_ty = child_fields.first().map(|f| &f.ty);
+
+        let build = match (&self.style, data_fields.len(), child_fields.len()) {
+            (Fields::Unit, 0, 0) => quote! { Ok(Self::#ident) },
+            (Fields::Unnamed(_), 1, 0) => {
+                let ty = &data_fields[0].ty;
+                // `children` is cloned, not moved, here: this expression is
+                // built as a `.map_err` closure argument, so it's evaluated
+                // (capturing `children`) every time this arm runs, even when
+                // `op_lit` is `None` and a later arm or the final fallback
+                // still needs `children`.
+                quote! { op.parse::<#ty>().map(Self::#ident).map_err(|_| egg::FromOpError::new(op, children.clone())) }
+            }
+            (Fields::Unnamed(_), 0, 1) => {
+                let ty = child_ty.unwrap();
+                quote! { Ok(Self::#ident(<#ty as egg::LanguageChildren>::from_vec(children))) }
+            }
+            (Fields::Unnamed(_), 1, 1) => {
+                let data_ty = &data_fields[0].ty;
+                let ty = child_ty.unwrap();
+                quote! {
+                    match op.parse::<#data_ty>() {
+                        Ok(data) => Ok(Self::#ident(data, <#ty as egg::LanguageChildren>::from_vec(children))),
+                        Err(_) => Err(egg::FromOpError::new(op, children)),
+                    }
+                }
+            }
+            _ => {
+                // Named-field variants (e.g. `Let { name, binding, body }`)
+                // and any tuple-variant shape outside the hardcoded arities
+                // above (e.g. `Custom(i32, bool)`). The at-most-one child
+                // field (see `VariantInfo::new`) takes the whole `children`
+                // vec via `LanguageChildren::from_vec`, the same as the
+                // tuple-variant arms above.
+                let is_unnamed = matches!(&self.style, Fields::Unnamed(_));
+                let field_inits = self.fields.iter().map(|f| {
+                    let binding = &f.binding;
+                    let ty = &f.ty;
+                    let value = match f.kind {
+                        FieldKind::Data => quote! { op.parse().map_err(|_| egg::FromOpError::new(op, children.clone()))? },
+                        FieldKind::Child => quote! { <#ty as egg::LanguageChildren>::from_vec(::std::mem::take(&mut children)) },
+                    };
+                    if is_unnamed {
+                        value
+                    } else {
+                        quote! { #binding: #value }
+                    }
+                });
+                let ctor = if is_unnamed {
+                    quote! { Self::#ident(#(#field_inits),*) }
+                } else {
+                    quote! { Self::#ident { #(#field_inits),* } }
+                };
+                // When this variant has an `#[op]` literal, this whole
+                // closure only runs inside a guarded `if op == "..."` that
+                // returns on success, so it's fine to consume `children` by
+                // value. Without one, it's tried unconditionally on every
+                // call (to see whether `op`/`children` happen to parse as
+                // this variant), so the outer `children` has to survive for
+                // whichever arm (or the final `Err`) runs next — clone it
+                // instead of moving it.
+                let take_children = if self.op.is_some() {
+                    quote! { children }
+                } else {
+                    quote! { children.clone() }
+                };
+                quote! {
+                    (|| -> ::std::result::Result<Self, egg::FromOpError> {
+                        let mut children = #take_children;
+                        Ok(#ctor)
+                    })()
+                }
+            }
+        };
+
+        match op_lit {
+            Some(op) => quote! {
+                if op == #op {
+                    return #build;
+                }
+            },
+            None => quote! {
+                if let ::std::result::Result::Ok(__egg_result) = (#build as ::std::result::Result<Self, egg::FromOpError>) {
+                    return Ok(__egg_result);
+                }
+            },
+        }
+    }
+}
+
+/// Parses an `#[op]` attribute's string literal, accepting both the
+/// `#[op = "..."]` (`Meta::NameValue`) and `#[op("...")]` (`Meta::List`) forms.
+fn parse_op_attr(attr: &syn::Attribute) -> syn::Result<syn::LitStr> {
+    match &attr.meta {
+        syn::Meta::NameValue(name_value) => match &name_value.value {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => Ok(lit.clone()),
+            other => Err(syn::Error::new_spanned(
+                other,
+                "expected a string literal: #[op = \"...\"]",
+            )),
+        },
+        syn::Meta::List(_) => attr.parse_args::<syn::LitStr>(),
+        other => Err(syn::Error::new_spanned(
+            other,
+            "expected `#[op = \"...\"]` or `#[op(\"...\")]`",
+        )),
+    }
+}
+
+/// Binds every field with a `prefix`-qualified name, used to produce two
+/// disjoint bindings of the same variant for the `matches()` arm.
+fn rebind(variant: &VariantInfo, prefix: &str) -> TokenStream2 {
+    let ident = &variant.ident;
+    match &variant.style {
+        Fields::Unit => quote! { Self::#ident },
+        Fields::Unnamed(_) => {
+            let bindings = variant
+                .fields
+                .iter()
+                .map(|f| quote::format_ident!("{}{}", prefix, f.binding));
+            quote! { Self::#ident(#(#bindings),*) }
+        }
+        Fields::Named(_) => {
+            let inits = variant.fields.iter().map(|f| {
+                let field = &f.binding;
+                let bound = quote::format_ident!("{}{}", prefix, f.binding);
+                quote! { #field: #bound }
+            });
+            quote! { Self::#ident { #(#inits),* } }
+        }
+    }
+}
+
+/// Classifies a field's type as an `Id` child or opaque data. Only the bare
+/// `Id` type, or one of the `LanguageChildren` container shapes with `Id` as
+/// its element type (`[Id; N]`, `Vec<Id>`, `Box<[Id]>`), counts as a child;
+/// everything else (including a `Vec`/`Box`/array over some other element
+/// type, e.g. `Vec<String>` or `[i32; 3]`) is opaque data, the same as a
+/// bare `i32` or `Symbol` field would be.
+fn classify(ty: &syn::Type) -> FieldKind {
+    if is_plain_id(ty) || is_array_or_slice(ty) {
+        FieldKind::Child
+    } else {
+        FieldKind::Data
+    }
+}
+
+fn is_plain_id(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(p) if p.path.is_ident("Id"))
+}
+
+/// True for `[Id; N]`, `Vec<Id>`, or `Box<[Id]>` specifically — not for an
+/// array/`Vec`/`Box` over any other element type.
+fn is_array_or_slice(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Array(array) => is_plain_id(&array.elem),
+        syn::Type::Path(p) => match p.path.segments.last() {
+            Some(seg) if seg.ident == "Vec" => single_angle_bracket_arg(seg)
+                .map(is_plain_id)
+                .unwrap_or(false),
+            Some(seg) if seg.ident == "Box" => single_angle_bracket_arg(seg)
+                .map(|arg| matches!(arg, syn::Type::Slice(slice) if is_plain_id(&slice.elem)))
+                .unwrap_or(false),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns a path segment's sole `<...>` type argument, e.g. the `Id` in
+/// `Vec<Id>` or the `[Id]` in `Box<[Id]>`.
+fn single_angle_bracket_arg(seg: &syn::PathSegment) -> Option<&syn::Type> {
+    match &seg.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// A compile-time-checked form of [`rewrite!`] for rules whose LHS/RHS (and
+/// any `if` conditions) are string patterns: it rejects, with a
+/// `compile_error!` pointing at the offending variable, any RHS that refers
+/// to a pattern variable the LHS doesn't bind. Falls back to the plain
+/// `rewrite!` behavior (a runtime panic) when a side is an arbitrary
+/// expression that can't be statically inspected.
+///
+/// [`rewrite!`]: https://docs.rs/egg/latest/egg/macro.rewrite.html
+#[proc_macro]
+pub fn rewrite_checked(input: TokenStream) -> TokenStream {
+    rewrite_checked::rewrite_checked(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ty(s: &str) -> syn::Type {
+        syn::parse_str(s).unwrap()
+    }
+
+    #[test]
+    fn plain_id_is_a_child() {
+        assert!(matches!(classify(&ty("Id")), FieldKind::Child));
+    }
+
+    #[test]
+    fn id_array_vec_and_boxed_slice_are_children() {
+        assert!(matches!(classify(&ty("[Id; 2]")), FieldKind::Child));
+        assert!(matches!(classify(&ty("Vec<Id>")), FieldKind::Child));
+        assert!(matches!(classify(&ty("Box<[Id]>")), FieldKind::Child));
+    }
+
+    #[test]
+    fn non_id_array_vec_and_boxed_slice_are_data() {
+        assert!(matches!(classify(&ty("[i32; 3]")), FieldKind::Data));
+        assert!(matches!(classify(&ty("Vec<String>")), FieldKind::Data));
+        assert!(matches!(classify(&ty("Box<[String]>")), FieldKind::Data));
+    }
+
+    #[test]
+    fn plain_scalar_is_data() {
+        assert!(matches!(classify(&ty("i32")), FieldKind::Data));
+        assert!(matches!(classify(&ty("Symbol")), FieldKind::Data));
+    }
+}
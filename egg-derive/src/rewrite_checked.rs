@@ -0,0 +1,212 @@
+//! `rewrite_checked!`: a drop-in, compile-time-checked cousin of `rewrite!`.
+//!
+//! `rewrite!("bad"; "?a" => "?x")` compiles today and only panics at runtime,
+//! inside `Rewrite::new`, with "refers to unbound var ?x". Since the LHS and
+//! RHS here are string literals, a proc macro can tokenize them into their
+//! s-expression form at compile time, collect the `?`-prefixed variable
+//! names on each side, and check `rhs_vars ⊆ lhs_vars` before the crate even
+//! finishes compiling. Any variable from an `if` condition that is itself a
+//! string pattern is folded into the set of variables the RHS must not
+//! exceed.
+//!
+//! When the RHS (or a condition) is an arbitrary expression rather than a
+//! string literal -- e.g. a custom [`Applier`] -- there is nothing to
+//! statically inspect, so `rewrite_checked!` falls back to emitting a plain
+//! call to [`rewrite!`] and lets the existing runtime check handle it.
+//!
+//! [`Applier`]: https://docs.rs/egg/latest/egg/trait.Applier.html
+//! [`rewrite!`]: https://docs.rs/egg/latest/egg/macro.rewrite.html
+
+use std::collections::BTreeSet;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Expr, LitStr, Token};
+
+/// A single `lhs => rhs` or `lhs <=> rhs` rule, optionally followed by `if`
+/// conditions, exactly like the grammar `rewrite!` accepts.
+struct RewriteInput {
+    name: Expr,
+    lhs: RuleSide,
+    bidirectional: bool,
+    rhs: RuleSide,
+    conds: Vec<Expr>,
+}
+
+/// Either side of a rule: a string pattern we can tokenize and check, or an
+/// arbitrary expression (a custom `Searcher`/`Applier`) we can't.
+enum RuleSide {
+    Pattern(LitStr),
+    Expr(Expr),
+}
+
+impl Parse for RewriteInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Expr = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let lhs = parse_side(input)?;
+        let bidirectional = if input.peek(Token![<=]) {
+            input.parse::<Token![<=]>()?;
+            input.parse::<Token![>]>()?;
+            true
+        } else {
+            input.parse::<Token![=>]>()?;
+            false
+        };
+        let rhs = parse_side(input)?;
+
+        let mut conds = Vec::new();
+        while input.peek(syn::Ident) {
+            let ident: syn::Ident = input.fork().parse()?;
+            if ident != "if" {
+                break;
+            }
+            input.parse::<syn::Ident>()?;
+            conds.push(input.parse()?);
+        }
+
+        Ok(RewriteInput {
+            name,
+            lhs,
+            bidirectional,
+            rhs,
+            conds,
+        })
+    }
+}
+
+fn parse_side(input: ParseStream) -> syn::Result<RuleSide> {
+    if input.peek(LitStr) {
+        Ok(RuleSide::Pattern(input.parse()?))
+    } else {
+        let content;
+        if input.peek(syn::token::Brace) {
+            syn::braced!(content in input);
+        } else if input.peek(syn::token::Paren) {
+            syn::parenthesized!(content in input);
+        } else {
+            return Ok(RuleSide::Expr(input.parse()?));
+        }
+        Ok(RuleSide::Expr(content.parse()?))
+    }
+}
+
+/// Splits an s-expression string into its whitespace/paren-delimited tokens
+/// and returns the `?`-prefixed ones, i.e. the pattern variables.
+fn pattern_vars(src: &str) -> BTreeSet<String> {
+    src.replace('(', " ( ")
+        .replace(')', " ) ")
+        .split_whitespace()
+        .filter(|tok| tok.starts_with('?') && tok.len() > 1)
+        .map(|tok| tok.to_string())
+        .collect()
+}
+
+fn side_vars(side: &RuleSide) -> Option<BTreeSet<String>> {
+    match side {
+        RuleSide::Pattern(lit) => Some(pattern_vars(&lit.value())),
+        RuleSide::Expr(_) => None,
+    }
+}
+
+fn side_tokens(side: &RuleSide) -> TokenStream2 {
+    match side {
+        RuleSide::Pattern(lit) => quote! { #lit },
+        RuleSide::Expr(expr) => quote! { { #expr } },
+    }
+}
+
+/// Checks that every variable in `rhs_vars` appears in `lhs_vars`, returning
+/// a `compile_error!` pointing at the rule's RHS literal if not.
+fn check_unbound(lhs_vars: &BTreeSet<String>, rhs: &RuleSide, rhs_vars: &BTreeSet<String>) -> Option<TokenStream2> {
+    let mut unbound: Vec<&String> = rhs_vars.difference(lhs_vars).collect();
+    if unbound.is_empty() {
+        return None;
+    }
+    unbound.sort();
+    let span = match rhs {
+        RuleSide::Pattern(lit) => lit.span(),
+        RuleSide::Expr(_) => Span::call_site(),
+    };
+    let message = format!(
+        "rewrite_checked!: variable(s) {} are not bound by the left-hand side",
+        unbound
+            .iter()
+            .map(|v| v.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    Some(quote::quote_spanned! { span => compile_error!(#message); })
+}
+
+pub fn rewrite_checked(input: TokenStream) -> TokenStream {
+    let rule = parse_macro_input!(input as RewriteInput);
+
+    let lhs_vars = side_vars(&rule.lhs);
+    let rhs_vars = side_vars(&rule.rhs);
+
+    // Only enforce the check when both sides (and any conditions) are
+    // statically-inspectable string patterns; otherwise fall back to the
+    // existing runtime behavior in `rewrite!`.
+    if let (Some(lhs_vars), Some(rhs_vars)) = (&lhs_vars, &rhs_vars) {
+        // Each direction is checked against the *other* side alone: in the
+        // `<=>` form that means rhs-as-written must be bound by lhs-as-written
+        // and vice versa, not against their union (which would trivially
+        // contain every variable on either side and never catch anything).
+        if let Some(error) = check_unbound(lhs_vars, &rule.rhs, rhs_vars) {
+            return error.into();
+        }
+        if rule.bidirectional {
+            if let Some(error) = check_unbound(rhs_vars, &rule.lhs, lhs_vars) {
+                return error.into();
+            }
+        }
+
+        // `if` conditions may legitimately reference a variable bound by
+        // either side of a bidirectional rule, so they're checked against
+        // the union.
+        let mut all_lhs_vars = lhs_vars.clone();
+        if rule.bidirectional {
+            all_lhs_vars.extend(rhs_vars.iter().cloned());
+        }
+        for cond in &rule.conds {
+            if let Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) = cond
+            {
+                let cond_vars = pattern_vars(&lit.value());
+                let unbound: Vec<_> = cond_vars.difference(&all_lhs_vars).collect();
+                if !unbound.is_empty() {
+                    let message = format!(
+                        "rewrite_checked!: condition refers to unbound variable(s): {}",
+                        unbound
+                            .iter()
+                            .map(|v| v.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                    return quote::quote_spanned! { lit.span() => compile_error!(#message); }.into();
+                }
+            }
+        }
+    }
+
+    let name = &rule.name;
+    let lhs_tokens = side_tokens(&rule.lhs);
+    let rhs_tokens = side_tokens(&rule.rhs);
+    let conds = &rule.conds;
+    let arrow = if rule.bidirectional {
+        quote! { <=> }
+    } else {
+        quote! { => }
+    };
+
+    let expanded = quote! {
+        egg::rewrite!(#name; #lhs_tokens #arrow #rhs_tokens #(if #conds)*)
+    };
+
+    expanded.into()
+}
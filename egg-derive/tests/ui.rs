@@ -0,0 +1,14 @@
+//! `trybuild` UI tests for `#[derive(Language)]` and `rewrite_checked!`:
+//! `tests/ui/*.rs` that should compile (and, for `pass_*` cases, run without
+//! panicking) plus the `fail_*` cases that should be rejected with the
+//! adjacent `.stderr`.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass_attrs.rs");
+    t.compile_fail("tests/ui/fail_multiple_children.rs");
+    t.pass("tests/ui/pass_rewrite_checked.rs");
+    t.compile_fail("tests/ui/fail_rewrite_checked_bidirectional.rs");
+    t.pass("tests/ui/pass_tuple_many_fields.rs");
+}
@@ -0,0 +1,9 @@
+use egg::Id;
+use egg_derive::Language;
+
+#[derive(Language, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+enum Lang {
+    Let { binding: Id, body: Id },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use egg_derive::rewrite_checked;
+
+fn main() {
+    // `?b` only appears on the left-hand side, so the `<=>` direction that
+    // treats "(+ ?a ?b)" as the *target* of a rewrite from "?a" leaves `?b`
+    // unbound. A check that only looks at the union of both sides' variables
+    // would miss this, since `?b` is in that union (it's on the LHS).
+    rewrite_checked!("bad"; "(+ ?a ?b)" <=> "?a");
+}
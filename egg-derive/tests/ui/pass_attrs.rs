@@ -0,0 +1,20 @@
+use egg::{FromOp, Id};
+use egg_derive::Language;
+
+#[derive(Language, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+enum Lang {
+    #[op = "+"]
+    Add([Id; 2]),
+    #[op("-")]
+    Sub([Id; 2]),
+    Num(i32),
+}
+
+fn main() {
+    assert_eq!(format!("{}", Lang::Add([Id::from(0), Id::from(1)])), "+");
+    assert_eq!(format!("{}", Lang::Sub([Id::from(0), Id::from(1)])), "-");
+    assert_eq!(
+        Lang::from_op("+", vec![Id::from(0), Id::from(1)]).unwrap(),
+        Lang::Add([Id::from(0), Id::from(1)])
+    );
+}
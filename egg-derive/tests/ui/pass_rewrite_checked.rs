@@ -0,0 +1,7 @@
+use egg_derive::rewrite_checked;
+
+fn main() {
+    // Commutativity: each side binds exactly the variables the other side
+    // needs, in both directions, so the bidirectional check passes.
+    let _ = rewrite_checked!("comm"; "(+ ?a ?b)" <=> "(+ ?b ?a)");
+}
@@ -0,0 +1,14 @@
+use egg::{FromOp, Id};
+use egg_derive::Language;
+
+#[derive(Language, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+enum Lang {
+    #[op = "+"]
+    Add([Id; 2]),
+    Custom(i32, bool),
+}
+
+fn main() {
+    assert_eq!(Lang::Custom(1, true), Lang::Custom(1, true));
+    assert!(Lang::from_op("nope", vec![]).is_err());
+}
@@ -88,6 +88,32 @@ pub trait SaturationNumber:
 }
 ```
 
+The generics grammar is not limited to a single simple trait bound per
+parameter: lifetimes, const generics, bounds with their own generic
+arguments (e.g. `FromStr<Err = String>`), and a trailing `where` clause are
+all accepted and threaded verbatim onto the generated `enum` and its
+`Language`, `Display`, and `FromOp` impls. In a `+`-separated bound list,
+only the *last* bound may carry its own generic arguments (as `FromStr<Err
+= String>` does above); earlier bounds in the same list must be plain
+trait names (e.g. `Clone + FromStr<Err = String>` is fine, but `FromStr<Err
+= String> + Clone` is not).
+
+# Example
+```ignore
+# use egg::*;
+# use std::fmt::Debug;
+define_language! {
+    enum Keyed<'a, T: Debug + Clone, const N: usize>
+    where
+        T: PartialEq,
+    {
+        "key" = Key(T),
+        "+" = Add([Id; N]),
+        Ref(std::marker::PhantomData<&'a ()>),
+    }
+}
+```
+
 [`Display`]: std::fmt::Display
 [`Debug`]: std::fmt::Debug
 [`FromStr`]: std::str::FromStr
@@ -95,24 +121,306 @@ pub trait SaturationNumber:
 **/
 #[macro_export]
 macro_rules! define_language {
-    ($(#[$meta:meta])* $vis:vis enum $name:ident $(<$($gen:ident $(: $bound:tt $(+ $bounds:tt)*)?),+>)? { $($variants:tt)* }) => {
-        $crate::__define_language!($(#[$meta])* $vis enum $name $(<$($gen $(: $bound $(+ $bounds)*)?),+>)? { $($variants)* } -> {} {} {} {} {} {});
+    // No generic parameters, no where clause.
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($variants:tt)* }) => {
+        $crate::__define_language!(
+            $(#[$meta])* $vis enum $name {} { $($variants)* } {} {} -> {} {} {} {} {} {}
+        );
+    };
+
+    // No generic parameters, but a where clause. Same ambiguity as below
+    // rules out matching `$(where $($where_clause:tt)*)? { $($variants:tt)*
+    // }` in one go, so hand the tokens after `where` to the same
+    // token-at-a-time collector generic languages use.
+    ($(#[$meta:meta])* $vis:vis enum $name:ident where $($rest:tt)+) => {
+        $crate::__define_language_where!(
+            { $($rest)+ } -> {} {} {}
+            $(#[$meta])* $vis enum $name
+        );
+    };
+
+    // Has generic parameters: hand everything after `<` to the classifier,
+    // which finds the matching `>` itself one parameter at a time.
+    ($(#[$meta:meta])* $vis:vis enum $name:ident < $($rest:tt)*) => {
+        $crate::__define_language_generics!(
+            { $($rest)* } -> {} {}
+            $(#[$meta])* $vis enum $name
+        );
+    };
+}
+
+/// Classifies and strips bounds from `define_language!`'s generic parameter
+/// list one parameter at a time, the same way [`__define_language`] walks
+/// variants one at a time. For each parameter it builds two token streams:
+/// the full declaration (verbatim, bounds and all, for the `enum` and
+/// `impl<...>` positions) and the bare name (for `$name<...>` usage
+/// positions, where bounds aren't allowed).
+///
+/// This can't start from a pre-sliced `{ $($generics:tt)* }` capturing
+/// everything between `<` and `>`: `tt` matches `>` just like any other
+/// token, so a rule that closes over both brackets in one go (`$(<
+/// $($generics:tt)* >)?`) is ambiguous -- `macro_rules` can't tell where the
+/// repetition should stop. Instead each rule below consumes exactly one
+/// parameter, with a real fragment specifier (`ident`/`lifetime`/`path`/`ty`)
+/// drawing the boundary, and looks at the single token right after it to
+/// decide whether to recurse (`,`) or hand off to
+/// [`__define_language_generics_done`] (`>`).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_language_generics {
+    // A lifetime parameter with a single lifetime bound, e.g. `'a: 'b`.
+    ({ $lt:lifetime : $lt_bound:lifetime , $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics!(
+            { $($rest)* } -> {$($full)* $lt: $lt_bound,} {$($bare)* $lt,} $($tail)*
+        );
+    };
+    ({ $lt:lifetime : $lt_bound:lifetime > $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics_done!(
+            { $($rest)* } -> {$($full)* $lt: $lt_bound,} {$($bare)* $lt,} $($tail)*
+        );
+    };
+
+    // A lifetime parameter with no bound.
+    ({ $lt:lifetime , $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics!(
+            { $($rest)* } -> {$($full)* $lt,} {$($bare)* $lt,} $($tail)*
+        );
+    };
+    ({ $lt:lifetime > $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics_done!(
+            { $($rest)* } -> {$($full)* $lt,} {$($bare)* $lt,} $($tail)*
+        );
+    };
+
+    // A const generic parameter, e.g. `const N: usize`.
+    ({ const $cname:ident : $cty:ty , $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics!(
+            { $($rest)* } -> {$($full)* const $cname: $cty,} {$($bare)* $cname,} $($tail)*
+        );
+    };
+    ({ const $cname:ident : $cty:ty > $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics_done!(
+            { $($rest)* } -> {$($full)* const $cname: $cty,} {$($bare)* $cname,} $($tail)*
+        );
+    };
+
+    // A type parameter with one or more bounds, e.g. `T: Clone + FromStr`.
+    // The bound list itself can't be matched in this one rule -- see
+    // [`__define_language_bounds`] -- so this just records the parameter
+    // name and hands the tokens after `:` to that classifier.
+    ({ $gen:ident : $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_bounds!(
+            { $($rest)* } -> {$($full)* $gen:} {$($bare)* $gen,} $($tail)*
+        );
+    };
+
+    // A type parameter with no bounds.
+    ({ $gen:ident , $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics!(
+            { $($rest)* } -> {$($full)* $gen,} {$($bare)* $gen,} $($tail)*
+        );
+    };
+    ({ $gen:ident > $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics_done!(
+            { $($rest)* } -> {$($full)* $gen,} {$($bare)* $gen,} $($tail)*
+        );
+    };
+}
+
+/// Classifies a single type parameter's `+`-separated bound list, one bound
+/// at a time, for [`__define_language_generics`].
+///
+/// Only the *last* bound may carry its own generic arguments (e.g.
+/// `FromStr<Err = String>`), since a `path` fragment can never be followed
+/// by `+`; earlier bounds must be plain trait names. Matching `$($b:ident
+/// +)* $bound:path` in one rule is itself ambiguous (`macro_rules` can't
+/// tell, from the first bound alone, whether it should feed the repetition
+/// or `$bound`), so this walks the list one token-tree-bounded bound at a
+/// time instead, the same way [`__define_language_generics`] walks
+/// parameters.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_language_bounds {
+    // Another bound follows; it must be a plain trait name.
+    ({ $bound:ident + $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_bounds!(
+            { $($rest)* } -> {$($full)* $bound +} {$($bare)*} $($tail)*
+        );
+    };
+
+    // The last bound, with more generic parameters following.
+    ({ $bound:path , $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics!(
+            { $($rest)* } -> {$($full)* $bound,} {$($bare)*} $($tail)*
+        );
+    };
+
+    // The last bound, at the end of the generic parameter list.
+    ({ $bound:path > $($rest:tt)* } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_generics_done!(
+            { $($rest)* } -> {$($full)* $bound,} {$($bare)*} $($tail)*
+        );
+    };
+}
+
+/// The `>` has been consumed by [`__define_language_generics`]; what's left
+/// is the optional `where` clause and the variant body, which this parses
+/// before handing everything off to [`__define_language`].
+///
+/// As with the generic parameter list, the where clause can't be captured
+/// as `$(where $($where_clause:tt)*)?` directly followed by the variants'
+/// `{ ... }` group: that group is itself a single `tt`, so the repetition
+/// can't unambiguously tell where to stop. [`__define_language_where`]
+/// walks the trailing tokens one at a time instead.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_language_generics_done {
+    // No where clause: the variants body follows `>` directly.
+    ({ { $($variants:tt)* } } -> {$($full:tt)*} {$($bare:tt)*}
+     $(#[$meta:meta])* $vis:vis enum $name:ident
+    ) => {
+        $crate::__define_language!(
+            $(#[$meta])* $vis enum $name {} { $($variants)* } {$($full)*} {$($bare)*} ->
+            {} {} {} {} {} {}
+        );
+    };
+
+    // A where clause precedes the variants body.
+    ({ where $($rest:tt)+ } -> {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_where!(
+            { $($rest)+ } -> {} {$($full)*} {$($bare)*} $($tail)*
+        );
+    };
+}
+
+/// Collects a `where` clause's tokens one at a time until only the
+/// variants' `{ ... }` group is left, the same "peel off the front, stop
+/// when one `tt` remains" trick [`__define_language_generics_done`] uses to
+/// find that group in the first place.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_language_where {
+    // Only the variants body is left; the where clause is fully collected.
+    ({ $variants:tt } -> {$($where_clause:tt)*} {$($full:tt)*} {$($bare:tt)*}
+     $(#[$meta:meta])* $vis:vis enum $name:ident
+    ) => {
+        $crate::__define_language!(
+            $(#[$meta])* $vis enum $name {$($where_clause)*} $variants {$($full)*} {$($bare)*} ->
+            {} {} {} {} {} {}
+        );
+    };
+
+    // More where-clause tokens remain; peel one off and keep going.
+    ({ $tok:tt $($rest:tt)+ } -> {$($where_clause:tt)*} {$($full:tt)*} {$($bare:tt)*} $($tail:tt)*) => {
+        $crate::__define_language_where!(
+            { $($rest)+ } -> {$($where_clause)* $tok} {$($full)*} {$($bare)*} $($tail)*
+        );
     };
 }
 
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __define_language {
-    // Rule for the end of the enum definition
-    ($(#[$meta:meta])* $vis:vis enum $name:ident $(<$($gen:ident $(: $bound:tt $(+ $bounds:tt)*)?),+>)? {} ->
+    // Rule for the end of the enum definition: no generics, no where clause.
+    ($(#[$meta:meta])* $vis:vis enum $name:ident {} {} {} {} ->
+     $decl:tt {$($matches:tt)*} $children:tt $children_mut:tt
+     $display:tt {$($from_op:tt)*}
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+        $vis enum $name $decl
+
+        impl $crate::Language for $name {
+            type Discriminant = std::mem::Discriminant<Self>;
+
+            #[inline(always)]
+            fn discriminant(&self) -> Self::Discriminant {
+                std::mem::discriminant(self)
+            }
+
+            #[inline(always)]
+            fn matches(&self, other: &Self) -> bool {
+                ::std::mem::discriminant(self) == ::std::mem::discriminant(other) &&
+                match (self, other) { $($matches)* _ => false }
+            }
+
+            fn children(&self) -> &[Id] { match self $children }
+            fn children_mut(&mut self) -> &mut [Id] { match self $children_mut }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match (self, f) $display
+            }
+        }
+
+        impl $crate::FromOp for $name {
+            type Error = $crate::FromOpError;
+
+            fn from_op(op: &str, children: ::std::vec::Vec<$crate::Id>) -> ::std::result::Result<Self, Self::Error> {
+                match (op, children) {
+                    $($from_op)*
+                    (op, children) => Err($crate::FromOpError::new(op, children)),
+                }
+            }
+        }
+    };
+
+    // Rule for the end of the enum definition: no generics, but a where clause.
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($where_clause:tt)+ } {} {} {} ->
+     $decl:tt {$($matches:tt)*} $children:tt $children_mut:tt
+     $display:tt {$($from_op:tt)*}
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+        $vis enum $name where $($where_clause)+ $decl
+
+        impl $crate::Language for $name where $($where_clause)+ {
+            type Discriminant = std::mem::Discriminant<Self>;
+
+            #[inline(always)]
+            fn discriminant(&self) -> Self::Discriminant {
+                std::mem::discriminant(self)
+            }
+
+            #[inline(always)]
+            fn matches(&self, other: &Self) -> bool {
+                ::std::mem::discriminant(self) == ::std::mem::discriminant(other) &&
+                match (self, other) { $($matches)* _ => false }
+            }
+
+            fn children(&self) -> &[Id] { match self $children }
+            fn children_mut(&mut self) -> &mut [Id] { match self $children_mut }
+        }
+
+        impl ::std::fmt::Display for $name where $($where_clause)+ {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match (self, f) $display
+            }
+        }
+
+        impl $crate::FromOp for $name where $($where_clause)+ {
+            type Error = $crate::FromOpError;
+
+            fn from_op(op: &str, children: ::std::vec::Vec<$crate::Id>) -> ::std::result::Result<Self, Self::Error> {
+                match (op, children) {
+                    $($from_op)*
+                    (op, children) => Err($crate::FromOpError::new(op, children)),
+                }
+            }
+        }
+    };
+
+    // Rule for the end of the enum definition: generics, no where clause.
+    ($(#[$meta:meta])* $vis:vis enum $name:ident {} {} { $($full:tt)+ } { $($bare:tt)* } ->
      $decl:tt {$($matches:tt)*} $children:tt $children_mut:tt
      $display:tt {$($from_op:tt)*}
     ) => {
         $(#[$meta])*
         #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
-        $vis enum $name $(<$($gen $(: $bound $(+ $bounds)*)?),+>)? $decl
+        $vis enum $name < $($full)+ > $decl
 
-        impl<$($($gen $(: $bound $(+ $bounds)*)?),+)? > $crate::Language for $name $(<$($gen),+>)? {
+        impl< $($full)+ > $crate::Language for $name < $($bare)* > {
             type Discriminant = std::mem::Discriminant<Self>;
 
             #[inline(always)]
@@ -130,13 +438,58 @@ macro_rules! __define_language {
             fn children_mut(&mut self) -> &mut [Id] { match self $children_mut }
         }
 
-        impl<$($($gen $(: $bound $(+ $bounds)*)?),+)? > ::std::fmt::Display for $name $(<$($gen),+>)? {
+        impl< $($full)+ > ::std::fmt::Display for $name < $($bare)* > {
             fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
                 match (self, f) $display
             }
         }
 
-        impl<$($($gen $(: $bound $(+ $bounds)*)?),+)? > $crate::FromOp for $name $(<$($gen),+>)? {
+        impl< $($full)+ > $crate::FromOp for $name < $($bare)* > {
+            type Error = $crate::FromOpError;
+
+            fn from_op(op: &str, children: ::std::vec::Vec<$crate::Id>) -> ::std::result::Result<Self, Self::Error> {
+                match (op, children) {
+                    $($from_op)*
+                    (op, children) => Err($crate::FromOpError::new(op, children)),
+                }
+            }
+        }
+    };
+
+    // Rule for the end of the enum definition: generics and a where clause.
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($where_clause:tt)+ } {} { $($full:tt)+ } { $($bare:tt)* } ->
+     $decl:tt {$($matches:tt)*} $children:tt $children_mut:tt
+     $display:tt {$($from_op:tt)*}
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+        $vis enum $name < $($full)+ > where $($where_clause)+ $decl
+
+        impl< $($full)+ > $crate::Language for $name < $($bare)* > where $($where_clause)+ {
+            type Discriminant = std::mem::Discriminant<Self>;
+
+            #[inline(always)]
+            fn discriminant(&self) -> Self::Discriminant {
+                std::mem::discriminant(self)
+            }
+
+            #[inline(always)]
+            fn matches(&self, other: &Self) -> bool {
+                ::std::mem::discriminant(self) == ::std::mem::discriminant(other) &&
+                match (self, other) { $($matches)* _ => false }
+            }
+
+            fn children(&self) -> &[Id] { match self $children }
+            fn children_mut(&mut self) -> &mut [Id] { match self $children_mut }
+        }
+
+        impl< $($full)+ > ::std::fmt::Display for $name < $($bare)* > where $($where_clause)+ {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match (self, f) $display
+            }
+        }
+
+        impl< $($full)+ > $crate::FromOp for $name < $($bare)* > where $($where_clause)+ {
             type Error = $crate::FromOpError;
 
             fn from_op(op: &str, children: ::std::vec::Vec<$crate::Id>) -> ::std::result::Result<Self, Self::Error> {
@@ -149,17 +502,17 @@ macro_rules! __define_language {
     };
 
     // Rule to handle string variants with no children
-    ($(#[$meta:meta])* $vis:vis enum $name:ident $(<$($gen:ident $(: $bound:tt $(+ $bounds:tt)*)?),+>)?
+    ($(#[$meta:meta])* $vis:vis enum $name:ident $where_clause:tt
      {
          $string:literal = $variant:ident,
          $($variants:tt)*
-     } ->
+     } $full:tt $bare:tt ->
      { $($decl:tt)* } { $($matches:tt)* } { $($children:tt)* } { $($children_mut:tt)* }
      { $($display:tt)* } { $($from_op:tt)* }
     ) => {
         $crate::__define_language!(
-            $(#[$meta])* $vis enum $name $(<$($gen $(: $bound $(+ $bounds)*)?),+>)?
-            { $($variants)* } ->
+            $(#[$meta])* $vis enum $name $where_clause
+            { $($variants)* } $full $bare ->
             { $($decl)* $variant, }
             { $($matches)* ($name::$variant, $name::$variant) => true, }
             { $($children)* $name::$variant => &[], }
@@ -170,17 +523,17 @@ macro_rules! __define_language {
     };
 
     // Rule to handle string variants with an array of child Ids
-    ($(#[$meta:meta])* $vis:vis enum $name:ident $(<$($gen:ident $(: $bound:tt $(+ $bounds:tt)*)?),+>)?
+    ($(#[$meta:meta])* $vis:vis enum $name:ident $where_clause:tt
      {
          $string:literal = $variant:ident ($ids:ty),
          $($variants:tt)*
-     } ->
+     } $full:tt $bare:tt ->
      { $($decl:tt)* } { $($matches:tt)* } { $($children:tt)* } { $($children_mut:tt)* }
      { $($display:tt)* } { $($from_op:tt)* }
     ) => {
         $crate::__define_language!(
-            $(#[$meta])* $vis enum $name $(<$($gen $(: $bound $(+ $bounds)*)?),+>)?
-            { $($variants)* } ->
+            $(#[$meta])* $vis enum $name $where_clause
+            { $($variants)* } $full $bare ->
             { $($decl)* $variant($ids), }
             { $($matches)* ($name::$variant(l), $name::$variant(r)) => $crate::LanguageChildren::len(l) == $crate::LanguageChildren::len(r), }
             { $($children)* $name::$variant(ids) => $crate::LanguageChildren::as_slice(ids), }
@@ -195,17 +548,17 @@ macro_rules! __define_language {
     };
 
     // Rule to handle data variants with a single field
-    ($(#[$meta:meta])* $vis:vis enum $name:ident $(<$($gen:ident $(: $bound:tt $(+ $bounds:tt)*)?),+>)?
+    ($(#[$meta:meta])* $vis:vis enum $name:ident $where_clause:tt
      {
          $variant:ident ($data:ty),
          $($variants:tt)*
-     } ->
+     } $full:tt $bare:tt ->
      { $($decl:tt)* } { $($matches:tt)* } { $($children:tt)* } { $($children_mut:tt)* }
      { $($display:tt)* } { $($from_op:tt)* }
     ) => {
         $crate::__define_language!(
-            $(#[$meta])* $vis enum $name $(<$($gen $(: $bound $(+ $bounds)*)?),+>)?
-            { $($variants)* } ->
+            $(#[$meta])* $vis enum $name $where_clause
+            { $($variants)* } $full $bare ->
             { $($decl)* $variant($data), }
             { $($matches)* ($name::$variant(data1), $name::$variant(data2)) => data1 == data2, }
             { $($children)* $name::$variant(_data) => &[], }
@@ -216,17 +569,17 @@ macro_rules! __define_language {
     };
 
     // Rule to handle data variants with a data field and an array of child Ids
-    ($(#[$meta:meta])* $vis:vis enum $name:ident $(<$($gen:ident $(: $bound:tt $(+ $bounds:tt)*)?),+>)?
+    ($(#[$meta:meta])* $vis:vis enum $name:ident $where_clause:tt
      {
          $variant:ident ($data:ty, $ids:ty),
          $($variants:tt)*
-     } ->
+     } $full:tt $bare:tt ->
      { $($decl:tt)* } { $($matches:tt)* } { $($children:tt)* } { $($children_mut:tt)* }
      { $($display:tt)* } { $($from_op:tt)* }
     ) => {
         $crate::__define_language!(
-            $(#[$meta])* $vis enum $name $(<$($gen $(: $bound $(+ $bounds)*)?),+>)?
-            { $($variants)* } ->
+            $(#[$meta])* $vis enum $name $where_clause
+            { $($variants)* } $full $bare ->
             { $($decl)* $variant($data, $ids), }
             { $($matches)* ($name::$variant(d1, l), $name::$variant(d2, r)) => d1 == d2 && $crate::LanguageChildren::len(l) == $crate::LanguageChildren::len(r), }
             { $($children)* $name::$variant(_, ids) => $crate::LanguageChildren::as_slice(ids), }
@@ -507,4 +860,82 @@ mod tests {
             panic!("Expected GenericLang::Add variant");
         }
     }
+
+    // A data field with a lifetime, for the lifetime-parameter grammar below.
+    #[derive(std::fmt::Debug, Clone, PartialEq, Eq, Ord, PartialOrd, std::hash::Hash)]
+    pub struct Tagged<'a>(std::marker::PhantomData<&'a ()>);
+
+    impl std::str::FromStr for Tagged<'_> {
+        type Err = String;
+        fn from_str(_s: &str) -> Result<Self, Self::Err> {
+            Ok(Tagged(std::marker::PhantomData))
+        }
+    }
+
+    impl std::fmt::Display for Tagged<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("tag")
+        }
+    }
+
+    // Exercises the rest of the widened generics grammar: a lifetime
+    // parameter, a const generic, and a multi-bound parameter whose last
+    // bound carries its own generic arguments, all in one parameter list.
+    define_language! {
+        enum Keyed<'a, T: Clone + SaturationNumber + std::fmt::Debug, const N: usize> {
+            Key(T),
+            "+" = Add([Id; N]),
+            Ref(Tagged<'a>),
+        }
+    }
+
+    #[test]
+    fn test_keyed_lang_display() {
+        assert_eq!(
+            format!("{}", Keyed::<CustomNumber, 2>::Add([1.into(), 2.into()])),
+            "+"
+        );
+        assert_eq!(format!("{}", Keyed::<CustomNumber, 2>::Ref(Tagged(std::marker::PhantomData))), "tag");
+    }
+
+    #[test]
+    fn test_keyed_lang_from_op() {
+        let key_op = Keyed::<CustomNumber, 2>::from_op("5", vec![]).unwrap();
+        assert!(matches!(key_op, Keyed::Key(_)));
+    }
+
+    // A `where` clause with no generic parameters on the enum itself.
+    define_language! {
+        enum NoGenericsWhere where CustomNumber: Clone {
+            Number(CustomNumber),
+            "+" = Add([Id; 2]),
+        }
+    }
+
+    #[test]
+    fn test_no_generics_where_display() {
+        assert_eq!(
+            format!("{}", NoGenericsWhere::Add([1.into(), 2.into()])),
+            "+"
+        );
+    }
+
+    // A `where` clause following a generic parameter list.
+    define_language! {
+        enum GenericsAndWhere<T>
+        where
+            T: SaturationNumber,
+        {
+            Number(T),
+            "+" = Add([Id; 2]),
+        }
+    }
+
+    #[test]
+    fn test_generics_and_where_display() {
+        assert_eq!(
+            format!("{}", GenericsAndWhere::<CustomNumber>::Add([1.into(), 2.into()])),
+            "+"
+        );
+    }
 }
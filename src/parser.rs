@@ -0,0 +1,380 @@
+/*! Pluggable concrete-syntax frontends for [`Language`]s.
+
+[`FromOp`] and [`RecExpr::from_str`] only understand fully-parenthesized
+prefix notation (`(+ a (* b c))`). That's a fine internal representation,
+but it's not how most people want to type an arithmetic or boolean
+expression. This module adds a [`LanguageParser`] trait so a language can
+plug in an alternative reader -- e.g. one built from an operator-precedence
+table -- that produces the same [`RecExpr<L>`] from infix/mixfix source like
+`a + b * c`, while the internal [`Language`] representation stays untouched.
+
+[`OperatorTable`] plus [`PrecedenceParser`] is a default, combinator-style
+implementation: register each operator's token, arity, precedence, and
+associativity, and the parser lowers the resulting parse tree into
+`RecExpr` nodes via [`FromOp`]. It's meant for simple expression languages;
+anything fancier (custom literals, mixfix brackets) should implement
+[`LanguageParser`] directly.
+
+# Example
+
+```
+# use egg::*;
+define_language! {
+    enum Math {
+        "+" = Add([Id; 2]),
+        "*" = Mul([Id; 2]),
+        Num(i32),
+        Symbol(Symbol),
+    }
+}
+
+let mut table = OperatorTable::new();
+table.infix("+", 1, Associativity::Left);
+table.infix("*", 2, Associativity::Left);
+let parser = PrecedenceParser::<Math>::new(table);
+
+let expr = parser.parse_expr("a + b * c").unwrap();
+assert_eq!(expr.to_string(), "(+ a (* b c))");
+```
+**/
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{FromOp, Id, Language, RecExpr};
+
+/// An error produced while parsing concrete syntax into a [`RecExpr`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl ParseError {
+    fn new(msg: impl Into<String>) -> Self {
+        ParseError(msg.into())
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A frontend that turns concrete syntax into a [`RecExpr<L>`].
+///
+/// Implement this to give a [`Language`] an alternative surface syntax
+/// (infix, mixfix, whatever) while keeping its internal representation --
+/// and everything downstream that consumes `RecExpr<L>` -- unchanged. This
+/// slots in alongside [`RecExpr::from_str`], which remains the
+/// prefix-notation reader.
+pub trait LanguageParser<L: Language> {
+    /// Parses `src` into a [`RecExpr<L>`], or an error describing where
+    /// parsing failed.
+    fn parse_expr(&self, src: &str) -> Result<RecExpr<L>, ParseError>;
+}
+
+/// Whether an infix operator groups to the left or the right when it
+/// appears multiple times at the same precedence, e.g. `a - b - c` as
+/// `(a - b) - c` (left) vs. `a = b = c` as `a = (b = c)` (right).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OperatorInfo {
+    precedence: u8,
+    associativity: Associativity,
+}
+
+/// A table mapping operator tokens to their precedence and associativity,
+/// consumed by [`PrecedenceParser`]. Each entry's op-string is later handed
+/// to [`FromOp::from_op`], so it must match what the language's `FromOp`
+/// impl (or `define_language!`/`#[derive(Language)]` output) expects.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorTable {
+    infix: HashMap<String, OperatorInfo>,
+}
+
+impl OperatorTable {
+    /// Creates an empty operator table.
+    pub fn new() -> Self {
+        OperatorTable::default()
+    }
+
+    /// Registers a binary infix operator, e.g. `table.infix("+", 1,
+    /// Associativity::Left)`. Higher precedence binds tighter.
+    pub fn infix(&mut self, op: &str, precedence: u8, associativity: Associativity) -> &mut Self {
+        self.infix.insert(
+            op.to_string(),
+            OperatorInfo {
+                precedence,
+                associativity,
+            },
+        );
+        self
+    }
+}
+
+/// A combinator-built [`LanguageParser`] driven by an [`OperatorTable`].
+///
+/// Parses source with a standard operator-precedence (Pratt) algorithm:
+/// atoms are symbols or number literals, infix operators combine atoms
+/// according to the table, and parentheses override precedence. The
+/// resulting parse tree is lowered into `RecExpr` nodes through
+/// [`FromOp::from_op`], so any binary operator registered here must have a
+/// matching two-child arm in the language's `FromOp` implementation.
+pub struct PrecedenceParser<L> {
+    table: OperatorTable,
+    _marker: std::marker::PhantomData<L>,
+}
+
+impl<L: Language + FromOp> PrecedenceParser<L> {
+    /// Creates a parser from an [`OperatorTable`].
+    pub fn new(table: OperatorTable) -> Self {
+        PrecedenceParser {
+            table,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<L: Language + FromOp> LanguageParser<L> for PrecedenceParser<L> {
+    fn parse_expr(&self, src: &str) -> Result<RecExpr<L>, ParseError> {
+        let tokens = tokenize(src)?;
+        let mut cursor = Cursor {
+            tokens: &tokens,
+            pos: 0,
+        };
+        let mut expr = RecExpr::default();
+        let root = parse_expr_bp(&mut cursor, &self.table, 0, &mut expr)?;
+        if cursor.pos != cursor.tokens.len() {
+            return Err(ParseError::new(format!(
+                "unexpected trailing input at token {}",
+                cursor.pos
+            )));
+        }
+        debug_assert_eq!(usize::from(root), expr.as_ref().len() - 1);
+        Ok(expr)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Symbol(String),
+    LParen,
+    RParen,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = src.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            _ => {
+                let mut sym = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    sym.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Symbol(sym));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Cursor<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<&'a Token> {
+        let tok = self.tokens.get(self.pos);
+        self.pos += 1;
+        tok
+    }
+}
+
+/// Pratt-parses one expression at binding power `min_bp`, lowering each
+/// completed subexpression into `expr` and returning its `Id`.
+fn parse_expr_bp<L: Language + FromOp>(
+    cursor: &mut Cursor,
+    table: &OperatorTable,
+    min_bp: u8,
+    expr: &mut RecExpr<L>,
+) -> Result<Id, ParseError> {
+    let mut lhs = parse_atom(cursor, table, expr)?;
+
+    loop {
+        let op = match cursor.peek() {
+            Some(Token::Symbol(s)) if table.infix.contains_key(s) => s.clone(),
+            _ => break,
+        };
+        let info = table.infix[&op];
+        if info.precedence < min_bp {
+            break;
+        }
+        cursor.bump();
+
+        let next_min_bp = match info.associativity {
+            Associativity::Left => info.precedence + 1,
+            Associativity::Right => info.precedence,
+        };
+        let rhs = parse_expr_bp(cursor, table, next_min_bp, expr)?;
+
+        let node = L::from_op(&op, vec![lhs, rhs])
+            .map_err(|e| ParseError::new(format!("invalid operator `{op}`: {e}")))?;
+        lhs = expr.add(node);
+    }
+
+    Ok(lhs)
+}
+
+fn parse_atom<L: Language + FromOp>(
+    cursor: &mut Cursor,
+    table: &OperatorTable,
+    expr: &mut RecExpr<L>,
+) -> Result<Id, ParseError> {
+    match cursor.bump() {
+        Some(Token::LParen) => {
+            let id = parse_expr_bp(cursor, table, 0, expr)?;
+            match cursor.bump() {
+                Some(Token::RParen) => Ok(id),
+                _ => Err(ParseError::new("expected closing paren")),
+            }
+        }
+        Some(Token::Symbol(s)) => {
+            let node = L::from_op(s, vec![])
+                .map_err(|e| ParseError::new(format!("invalid atom `{s}`: {e}")))?;
+            Ok(expr.add(node))
+        }
+        Some(Token::RParen) => Err(ParseError::new("unexpected closing paren")),
+        None => Err(ParseError::new("unexpected end of input")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    define_language! {
+        enum Math {
+            "+" = Add([Id; 2]),
+            "-" = Sub([Id; 2]),
+            "=" = Assign([Id; 2]),
+            "*" = Mul([Id; 2]),
+            Num(i32),
+            Symbol(Symbol),
+        }
+    }
+
+    // No catch-all `Symbol`-like field, so an atom that isn't a number fails
+    // to parse -- used to exercise the "invalid atom" error path below.
+    define_language! {
+        enum NumOnly {
+            "+" = Add([Id; 2]),
+            Num(i32),
+        }
+    }
+
+    fn parser() -> PrecedenceParser<Math> {
+        let mut table = OperatorTable::new();
+        table.infix("+", 1, Associativity::Left);
+        table.infix("-", 1, Associativity::Left);
+        table.infix("*", 2, Associativity::Left);
+        table.infix("=", 0, Associativity::Right);
+        PrecedenceParser::new(table)
+    }
+
+    #[test]
+    fn precedence_binds_tighter_operator_first() {
+        let expr = parser().parse_expr("a + b * c").unwrap();
+        assert_eq!(expr.to_string(), "(+ a (* b c))");
+    }
+
+    #[test]
+    fn left_associative_operator_groups_left() {
+        let expr = parser().parse_expr("a - b - c").unwrap();
+        assert_eq!(expr.to_string(), "(- (- a b) c)");
+    }
+
+    #[test]
+    fn right_associative_operator_groups_right() {
+        let expr = parser().parse_expr("a = b = c").unwrap();
+        assert_eq!(expr.to_string(), "(= a (= b c))");
+    }
+
+    #[test]
+    fn parens_override_precedence() {
+        let expr = parser().parse_expr("(a + b) * c").unwrap();
+        assert_eq!(expr.to_string(), "(* (+ a b) c)");
+    }
+
+    #[test]
+    fn nested_parens_parse() {
+        let expr = parser().parse_expr("((a + b)) * (c - (d))").unwrap();
+        assert_eq!(expr.to_string(), "(* (+ a b) (- c d))");
+    }
+
+    #[test]
+    fn single_atom_parses() {
+        let expr = parser().parse_expr("42").unwrap();
+        assert_eq!(expr.to_string(), "42");
+    }
+
+    #[test]
+    fn unexpected_closing_paren_is_an_error() {
+        let err = parser().parse_expr(")").unwrap_err();
+        assert_eq!(err.to_string(), "unexpected closing paren");
+    }
+
+    #[test]
+    fn missing_closing_paren_is_an_error() {
+        let err = parser().parse_expr("(a + b").unwrap_err();
+        assert_eq!(err.to_string(), "expected closing paren");
+    }
+
+    #[test]
+    fn trailing_input_after_a_complete_expression_is_an_error() {
+        let err = parser().parse_expr("a + b c").unwrap_err();
+        assert!(err.to_string().contains("unexpected trailing input"), "{err}");
+    }
+
+    #[test]
+    fn unknown_atom_is_an_error() {
+        let mut table = OperatorTable::new();
+        table.infix("+", 1, Associativity::Left);
+        let parser = PrecedenceParser::<NumOnly>::new(table);
+        let err = parser.parse_expr("x").unwrap_err();
+        assert!(err.to_string().contains("invalid atom"), "{err}");
+    }
+
+    #[test]
+    fn empty_input_is_an_error() {
+        let err = parser().parse_expr("").unwrap_err();
+        assert_eq!(err.to_string(), "unexpected end of input");
+    }
+}
@@ -0,0 +1,417 @@
+/*! An interactive REPL for exploring equality saturation one step at a time.
+
+`Repl` drives an [`EGraph`] for any [`Language`] that also implements
+[`FromOp`] and [`Display`], together with an [`Analysis`], and lets you type
+commands instead of writing a throwaway `main` every time you want to see
+why a rule did or didn't fire. It understands:
+
+- `add <sexpr>` -- parse a [`RecExpr`] and insert it into the e-graph.
+- `run <rule-name>` -- apply a single rewrite, by name, from the rule set
+  the REPL was constructed with.
+- `step` -- run one iteration of [`Runner`] over every rule in the set.
+- `extract <id>` -- run an [`Extractor`] (by default with
+  [`AstSize`](crate::AstSize)) and print the best term for an e-class.
+- `find <pattern>` -- run a [`Searcher`] and list its matches/substitutions.
+- `dump` -- print e-class/e-node counts and the union-find state.
+
+S-expressions may span multiple lines; a line is only dispatched as a
+command once its parentheses balance. Command history is appended to a
+dotfile (`~/.egg_repl_history` by default) so it persists across sessions.
+
+This module is only compiled with the `repl` feature enabled, since it
+pulls in the extra commands but isn't needed by the core library.
+
+# Example
+
+```no_run
+# use egg::*;
+define_language! {
+    enum SimpleLanguage {
+        "+" = Add([Id; 2]),
+        Num(i32),
+    }
+}
+let rules: Vec<Rewrite<SimpleLanguage, ()>> = vec![];
+let mut repl = Repl::new(rules);
+repl.run_interactive();
+```
+
+[`Display`]: std::fmt::Display
+**/
+
+use std::fmt::Display;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::{Analysis, AstSize, EGraph, Extractor, FromOp, Id, Language, Pattern, RecExpr, Rewrite, Runner, Searcher};
+
+/// An interactive, step-by-step driver for an [`EGraph`].
+///
+/// See the [module docs](self) for the commands it understands.
+pub struct Repl<L: Language, N: Analysis<L> = ()> {
+    egraph: EGraph<L, N>,
+    rules: Vec<Rewrite<L, N>>,
+    history_path: PathBuf,
+}
+
+/// The result of running one REPL command, printed to stdout by
+/// [`Repl::run_interactive`] but also returned from [`Repl::eval`] so the
+/// REPL can be driven programmatically (e.g. in tests).
+#[derive(Debug)]
+pub enum ReplOutput {
+    /// The id the added expression was inserted at.
+    Added(Id),
+    /// How many e-classes changed on the last `run`/`step`.
+    Stepped { modified: bool },
+    /// The best term found by `extract`, and its cost.
+    Extracted(String, f64),
+    /// The substitutions found by `find`, one line each.
+    Found(Vec<String>),
+    /// The e-class/e-node counts reported by `dump`.
+    Dumped { classes: usize, nodes: usize },
+    /// A plain message, e.g. an error or confirmation.
+    Message(String),
+}
+
+impl<L, N> Repl<L, N>
+where
+    L: Language + FromOp + Display,
+    N: Analysis<L> + Default,
+{
+    /// Creates a REPL with an empty e-graph and the given rewrite rules.
+    pub fn new(rules: Vec<Rewrite<L, N>>) -> Self {
+        Self::with_history_path(rules, default_history_path())
+    }
+
+    /// Like [`Repl::new`], but with an explicit history file path.
+    pub fn with_history_path(rules: Vec<Rewrite<L, N>>, history_path: PathBuf) -> Self {
+        Repl {
+            egraph: EGraph::default(),
+            rules,
+            history_path,
+        }
+    }
+
+    /// Parses and evaluates a single command line, returning its result.
+    pub fn eval(&mut self, line: &str) -> Result<ReplOutput, String> {
+        let line = line.trim();
+        let (cmd, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        match cmd {
+            "add" => {
+                let expr: RecExpr<L> = rest
+                    .parse()
+                    .map_err(|e| format!("failed to parse expression: {e}"))?;
+                let id = self.egraph.add_expr(&expr);
+                // `add_expr` leaves the e-graph dirty; `run`/`find` search it
+                // and panic on a dirty e-graph, so rebuild before returning.
+                self.egraph.rebuild();
+                Ok(ReplOutput::Added(id))
+            }
+            "run" => {
+                let rule = self
+                    .rules
+                    .iter()
+                    .find(|r| r.name.as_str() == rest)
+                    .ok_or_else(|| format!("no rule named {rest:?}"))?
+                    .clone();
+                let modified = rule.search(&self.egraph).into_iter().fold(false, |acc, m| {
+                    let applied = rule.apply(&mut self.egraph, &[m]);
+                    acc || !applied.is_empty()
+                });
+                self.egraph.rebuild();
+                Ok(ReplOutput::Stepped { modified })
+            }
+            "step" => {
+                let before = self.egraph.total_number_of_nodes();
+                let egraph = std::mem::take(&mut self.egraph);
+                let runner = Runner::default()
+                    .with_egraph(egraph)
+                    .with_iter_limit(1)
+                    .run(&self.rules);
+                self.egraph = runner.egraph;
+                let modified = self.egraph.total_number_of_nodes() != before;
+                Ok(ReplOutput::Stepped { modified })
+            }
+            "extract" => {
+                let n: usize = rest
+                    .parse()
+                    .map_err(|e| format!("invalid id {rest:?}: {e}"))?;
+                // `total_size()`, not `number_of_classes()`: ids aren't
+                // renumbered when e-classes merge, so a valid (if
+                // non-canonical) id can be as large as the total number of
+                // e-nodes ever added, even once unioning has shrunk the
+                // e-class count below it.
+                let total = self.egraph.total_size();
+                if n >= total {
+                    return Err(format!("no e-class with id {n} ({total} e-node id(s) exist)"));
+                }
+                let extractor = Extractor::new(&self.egraph, AstSize);
+                let (cost, best) = extractor.find_best(n.into());
+                Ok(ReplOutput::Extracted(best.to_string(), cost as f64))
+            }
+            "find" => {
+                let pattern: Pattern<L> = rest
+                    .parse()
+                    .map_err(|e| format!("failed to parse pattern: {e}"))?;
+                let lines = pattern
+                    .search(&self.egraph)
+                    .iter()
+                    .flat_map(|m| m.substs.iter())
+                    .map(|subst| format!("{subst:?}"))
+                    .collect();
+                Ok(ReplOutput::Found(lines))
+            }
+            "dump" => Ok(ReplOutput::Dumped {
+                classes: self.egraph.number_of_classes(),
+                nodes: self.egraph.total_number_of_nodes(),
+            }),
+            "" => Ok(ReplOutput::Message(String::new())),
+            other => Err(format!("unknown command: {other}")),
+        }
+    }
+
+    /// Runs the REPL against stdin/stdout until EOF, echoing results and
+    /// persisting each non-empty line to the history file. Multi-line
+    /// s-expressions (unbalanced parens) are buffered until complete.
+    pub fn run_interactive(&mut self) {
+        use std::io::BufRead;
+        let stdin = io::stdin();
+        let mut pending = String::new();
+
+        loop {
+            print!("{}", if pending.is_empty() { "egg> " } else { "...> " });
+            let _ = io::stdout().flush();
+
+            let mut line = String::new();
+            match stdin.lock().read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+
+            pending.push_str(&line);
+            if paren_balance(&pending) > 0 {
+                continue;
+            }
+
+            let command = std::mem::take(&mut pending);
+            self.append_history(command.trim());
+
+            match self.eval(&command) {
+                Ok(output) => print_output(&output),
+                Err(e) => eprintln!("error: {e}"),
+            }
+        }
+    }
+
+    fn append_history(&self, line: &str) {
+        if line.is_empty() {
+            return;
+        }
+        if let Ok(mut file) = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.history_path)
+        {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+}
+
+fn print_output(output: &ReplOutput) {
+    match output {
+        ReplOutput::Added(id) => println!("added at e-class {id}"),
+        ReplOutput::Stepped { modified } => {
+            println!("{}", if *modified { "modified" } else { "no change" })
+        }
+        ReplOutput::Extracted(best, cost) => println!("{best} (cost {cost})"),
+        ReplOutput::Found(lines) => {
+            if lines.is_empty() {
+                println!("no matches");
+            } else {
+                lines.iter().for_each(|line| println!("{line}"));
+            }
+        }
+        ReplOutput::Dumped { classes, nodes } => {
+            println!("{classes} e-classes, {nodes} e-nodes")
+        }
+        ReplOutput::Message(msg) => {
+            if !msg.is_empty() {
+                println!("{msg}")
+            }
+        }
+    }
+}
+
+fn paren_balance(s: &str) -> i64 {
+    s.chars().fold(0i64, |acc, c| match c {
+        '(' => acc + 1,
+        ')' => acc - 1,
+        _ => acc,
+    })
+}
+
+fn default_history_path() -> PathBuf {
+    dirs_home().map_or_else(|| PathBuf::from(".egg_repl_history"), |home| home.join(".egg_repl_history"))
+}
+
+fn dirs_home() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    define_language! {
+        enum Simple {
+            "+" = Add([Id; 2]),
+            Num(i32),
+        }
+    }
+
+    fn repl(name: &str) -> Repl<Simple, ()> {
+        Repl::with_history_path(
+            vec![rewrite!("comm"; "(+ ?a ?b)" => "(+ ?b ?a)")],
+            std::env::temp_dir().join(format!("egg_repl_test_{name}")),
+        )
+    }
+
+    #[test]
+    fn add_inserts_the_expression() {
+        let mut r = repl("add");
+        let out = r.eval("add (+ 1 2)").unwrap();
+        assert!(matches!(out, ReplOutput::Added(_)));
+    }
+
+    #[test]
+    fn add_rejects_unparseable_expression() {
+        let mut r = repl("add_bad");
+        let err = r.eval("add (+ 1").unwrap_err();
+        assert!(err.contains("failed to parse expression"), "{err}");
+    }
+
+    #[test]
+    fn run_applies_a_named_rule() {
+        let mut r = repl("run");
+        r.eval("add (+ 1 2)").unwrap();
+        let out = r.eval("run comm").unwrap();
+        match out {
+            ReplOutput::Stepped { modified } => assert!(modified),
+            other => panic!("expected Stepped, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn run_reports_no_change_once_saturated() {
+        let mut r = repl("run_saturated");
+        r.eval("add (+ 1 2)").unwrap();
+        r.eval("run comm").unwrap();
+        let out = r.eval("run comm").unwrap();
+        match out {
+            ReplOutput::Stepped { modified } => assert!(!modified),
+            other => panic!("expected Stepped, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn run_rejects_unknown_rule_name() {
+        let mut r = repl("run_unknown");
+        let err = r.eval("run nope").unwrap_err();
+        assert!(err.contains("nope"), "{err}");
+    }
+
+    #[test]
+    fn step_runs_one_runner_iteration() {
+        let mut r = repl("step");
+        r.eval("add (+ 1 2)").unwrap();
+        let out = r.eval("step").unwrap();
+        match out {
+            ReplOutput::Stepped { modified } => assert!(modified),
+            other => panic!("expected Stepped, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn extract_returns_the_best_term_for_a_valid_id() {
+        let mut r = repl("extract_ok");
+        let id = match r.eval("add (+ 1 2)").unwrap() {
+            ReplOutput::Added(id) => id,
+            other => panic!("expected Added, got {other:?}"),
+        };
+        let out = r.eval(&format!("extract {}", usize::from(id))).unwrap();
+        match out {
+            ReplOutput::Extracted(s, _) => assert_eq!(s, "(+ 1 2)"),
+            other => panic!("expected Extracted, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn extract_rejects_an_out_of_range_id_instead_of_panicking() {
+        let mut r = repl("extract_oor");
+        r.eval("add (+ 1 2)").unwrap();
+        let err = r.eval("extract 999").unwrap_err();
+        assert!(err.contains("999"), "error should mention the bad id: {err}");
+    }
+
+    #[test]
+    fn find_lists_matching_substitutions() {
+        let mut r = repl("find");
+        r.eval("add (+ 1 2)").unwrap();
+        let out = r.eval("find (+ ?a ?b)").unwrap();
+        match out {
+            ReplOutput::Found(lines) => assert_eq!(lines.len(), 1),
+            other => panic!("expected Found, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn find_returns_no_lines_without_a_match() {
+        let mut r = repl("find_none");
+        r.eval("add 1").unwrap();
+        let out = r.eval("find (+ ?a ?b)").unwrap();
+        match out {
+            ReplOutput::Found(lines) => assert!(lines.is_empty()),
+            other => panic!("expected Found, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn dump_reports_class_and_node_counts() {
+        let mut r = repl("dump");
+        r.eval("add (+ 1 2)").unwrap();
+        let out = r.eval("dump").unwrap();
+        match out {
+            ReplOutput::Dumped { classes, nodes } => {
+                assert_eq!(nodes, 3);
+                assert!(classes <= nodes);
+            }
+            other => panic!("expected Dumped, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn empty_line_is_a_no_op() {
+        let mut r = repl("empty");
+        let out = r.eval("").unwrap();
+        assert!(matches!(out, ReplOutput::Message(ref m) if m.is_empty()));
+    }
+
+    #[test]
+    fn unknown_command_is_an_error() {
+        let mut r = repl("unknown");
+        let err = r.eval("frobnicate").unwrap_err();
+        assert!(err.contains("frobnicate"), "{err}");
+    }
+
+    #[test]
+    fn paren_balance_tracks_nesting() {
+        assert_eq!(paren_balance("(+ 1 2)"), 0);
+        assert_eq!(paren_balance("(+ 1"), 1);
+        assert_eq!(paren_balance("(+ 1 (* 2 3)"), 1);
+        assert_eq!(paren_balance(""), 0);
+    }
+}